@@ -0,0 +1,126 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{Zero, One};
+
+use crate::{Curve, EcError, Point, Scalar, utils};
+
+/// Truncate `hash` to (at most) the bit length of `C::n()`, as required
+/// before folding a message hash into an ECDSA signature or verification.
+fn truncate_to_n<C: Curve>(hash: &BigInt) -> BigInt {
+    let n_bits = C::n().bits();
+    let hash_bits = hash.bits();
+
+    if hash_bits <= n_bits {
+        hash.clone()
+    } else {
+        hash >> (hash_bits - n_bits) as usize
+    }
+}
+
+/// Sign `msg_hash` with `priv_key` using the nonce `k`. `k` must be
+/// chosen uniformly from `[1, n-1]` and never reused across signatures.
+/// Returns `None` if `k` happens to produce a degenerate signature
+/// (`r == 0` or `s == 0`), in which case the caller should retry with a
+/// fresh `k`. When `low_s` is set, `s` is normalized to `min(s, n - s)`,
+/// as required by consumers such as Bitcoin.
+pub fn sign<C: Curve>(priv_key: &Scalar<C>, msg_hash: &BigInt, k: &Scalar<C>, low_s: bool) -> Option<(BigInt, BigInt)> {
+    let n = C::n();
+
+    let (rx, _) = (Point::<C>::generator() * k.value()).ok()?.value()?;
+    let r = rx.mod_floor(&n);
+    if r.is_zero() {
+        return None;
+    }
+
+    let z = truncate_to_n::<C>(msg_hash);
+    let k_inv = utils::inverse_mod(k.value(), n.clone()).ok()?;
+    let mut s = (k_inv * (z + &r * priv_key.value())).mod_floor(&n);
+    if s.is_zero() {
+        return None;
+    }
+
+    if low_s {
+        let half_n = &n / BigInt::from(2u32);
+        if s > half_n {
+            s = n - s;
+        }
+    }
+
+    Some((r, s))
+}
+
+/// Verify that `sig` is a valid ECDSA signature over `msg_hash` by
+/// `pub_key`.
+pub fn verify<C: Curve>(pub_key: &Point<C>, msg_hash: &BigInt, sig: (&BigInt, &BigInt)) -> bool {
+    let n = C::n();
+    let (r, s) = sig;
+
+    if *r < BigInt::one() || *r >= n || *s < BigInt::one() || *s >= n {
+        return false;
+    }
+
+    let z = truncate_to_n::<C>(msg_hash);
+    let w = match utils::inverse_mod(s.clone(), n.clone()) {
+        Ok(w) => w,
+        Err(_) => return false,
+    };
+    let u1 = (&z * &w).mod_floor(&n);
+    let u2 = (r * &w).mod_floor(&n);
+
+    let p: Result<Point<C>, EcError> = (|| {
+        let p1 = Point::generator() * u1;
+        let p2 = pub_key.clone() * u2;
+        p1? + p2?
+    })();
+
+    match p.ok().and_then(Point::value) {
+        None => false,
+        Some((x, _)) => x.mod_floor(&n) == *r,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_curves::{TestCurve, P256K1Curve};
+
+    #[test]
+    fn sign_and_verify() {
+        let priv_key = Scalar::<TestCurve>::new(BigInt::from(7u32));
+        let pub_key = (Point::<TestCurve>::generator() * priv_key.value()).unwrap();
+        let msg_hash = BigInt::from(9u32);
+
+        let k = Scalar::<TestCurve>::new(BigInt::from(5u32));
+        let (r, s) = sign(&priv_key, &msg_hash, &k, false).unwrap();
+
+        assert!(verify(&pub_key, &msg_hash, (&r, &s)));
+        assert!(!verify(&pub_key, &(msg_hash + BigInt::one()), (&r, &s)));
+    }
+
+    #[test]
+    fn sign_low_s() {
+        let priv_key = Scalar::<TestCurve>::new(BigInt::from(7u32));
+        let msg_hash = BigInt::from(9u32);
+        let k = Scalar::<TestCurve>::new(BigInt::from(5u32));
+
+        let (_, s) = sign(&priv_key, &msg_hash, &k, true).unwrap();
+        assert!(s <= &TestCurve::n() / BigInt::from(2u32));
+    }
+
+    #[test]
+    fn secp256k1_sign_and_verify() {
+        use num_traits::Num;
+
+        let priv_key = Scalar::<P256K1Curve>::new(BigInt::from_str_radix(
+            "EBB2C082FD7727890A28AC82F6BDF97BAD8DE9F5D7C9028692DE1A255CAD3E0", 16).unwrap());
+        let pub_key = (Point::<P256K1Curve>::generator() * priv_key.value()).unwrap();
+        let msg_hash = BigInt::from_str_radix(
+            "4B688DF40BCEDBE641DDB16FF0A1842D9C67EA1C3BF63F3E0471BAA664531D1", 16).unwrap();
+        let k = Scalar::<P256K1Curve>::new(BigInt::from_str_radix(
+            "49A0D7B786EC9CDE0D0721D72804BEFD06571C974B191EFB42ECF322BA9DD5A", 16).unwrap());
+
+        let (r, s) = sign(&priv_key, &msg_hash, &k, false).unwrap();
+        assert!(verify(&pub_key, &msg_hash, (&r, &s)));
+        assert!(!verify(&pub_key, &(msg_hash + BigInt::one()), (&r, &s)));
+    }
+}
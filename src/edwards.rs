@@ -0,0 +1,50 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{Zero, One};
+
+use crate::{Curve, EcError, Point, PointValue, utils};
+
+/// The identity element of the twisted Edwards group, `(0, 1)`.
+pub(crate) fn identity<C: Curve>() -> Point<C> {
+    Point::from(PointValue::Value { x: BigInt::zero(), y: BigInt::one() })
+}
+
+pub(crate) fn is_valid<C: Curve>(x: &BigInt, y: &BigInt) -> bool {
+    let p = C::p();
+    let lhs = (&C::a() * x * x + y * y).mod_floor(&p);
+    let rhs = (BigInt::one() + &C::b() * x * x * y * y).mod_floor(&p);
+    lhs == rhs
+}
+
+/// Unified twisted Edwards point addition, `ax^2 + y^2 = 1 + d*x^2*y^2`
+/// with identity `(0, 1)`. Unlike short Weierstrass addition, this one
+/// formula also covers doubling and the identity without special-casing.
+pub(crate) fn add<C: Curve>(p1: &Point<C>, p2: &Point<C>) -> Result<Point<C>, EcError> {
+    let (x1, y1) = affine_or_identity(p1);
+    let (x2, y2) = affine_or_identity(p2);
+
+    let p = C::p();
+    let d = C::b();
+
+    let x1y2 = &x1 * &y2;
+    let y1x2 = &y1 * &x2;
+    let y1y2 = &y1 * &y2;
+    let x1x2 = &x1 * &x2;
+    let dxy = (&d * &x1x2 * &y1y2).mod_floor(&p);
+
+    let x3_den = (BigInt::one() + &dxy).mod_floor(&p);
+    let y3_den = (BigInt::one() - &dxy).mod_floor(&p);
+    let x3_den_inv = utils::inverse_mod(x3_den, p.clone())?;
+    let y3_den_inv = utils::inverse_mod(y3_den, p.clone())?;
+    let x3 = ((x1y2 + y1x2) * x3_den_inv).mod_floor(&p);
+    let y3 = ((y1y2 - C::a() * x1x2) * y3_den_inv).mod_floor(&p);
+
+    Ok(Point::from(PointValue::Value { x: x3, y: y3 }))
+}
+
+fn affine_or_identity<C: Curve>(p: &Point<C>) -> (BigInt, BigInt) {
+    match p.value {
+        PointValue::Infinity => (BigInt::zero(), BigInt::one()),
+        PointValue::Value { ref x, ref y } => (x.clone(), y.clone()),
+    }
+}
@@ -0,0 +1,25 @@
+use core::fmt;
+
+/// Errors arising from curve arithmetic and point encoding.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum EcError {
+    /// The value has no inverse under the given modulus, i.e. the two
+    /// share a common factor greater than one.
+    NotInvertible,
+    /// The point's coordinates do not satisfy the curve equation.
+    PointNotOnCurve,
+    /// The byte encoding was malformed or used an unrecognized prefix.
+    InvalidEncoding,
+}
+
+impl fmt::Display for EcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EcError::NotInvertible => write!(f, "value has no inverse under the given modulus"),
+            EcError::PointNotOnCurve => write!(f, "point does not satisfy the curve equation"),
+            EcError::InvalidEncoding => write!(f, "malformed point encoding"),
+        }
+    }
+}
+
+impl std::error::Error for EcError {}
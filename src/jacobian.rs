@@ -0,0 +1,117 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{Zero, One};
+use core::marker::PhantomData;
+
+use crate::{Curve, EcError, Point, PointValue, utils};
+
+/// A point in Jacobian projective coordinates `(X, Y, Z)`, standing for
+/// the affine point `(X/Z^2, Y/Z^3)`. Repeated doublings and additions
+/// stay in this representation so that the (expensive) modular inversion
+/// needed to get back to affine coordinates only has to happen once, at
+/// the end of a scalar multiplication, instead of on every step.
+#[derive(Clone)]
+pub(crate) struct Jacobian<C: Curve> {
+    x: BigInt,
+    y: BigInt,
+    z: BigInt,
+    _marker: PhantomData<C>,
+}
+
+impl<C: Curve> Jacobian<C> {
+    pub fn infinity() -> Self {
+        Self {
+            x: BigInt::one(),
+            y: BigInt::one(),
+            z: BigInt::zero(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    pub fn from_affine(p: &Point<C>) -> Self {
+        match p.value {
+            PointValue::Infinity => Self::infinity(),
+            PointValue::Value { ref x, ref y } => Self {
+                x: x.clone(),
+                y: y.clone(),
+                z: BigInt::one(),
+                _marker: PhantomData,
+            },
+        }
+    }
+
+    pub fn to_affine(&self) -> Result<Point<C>, EcError> {
+        if self.is_infinity() {
+            return Ok(Point::infinity());
+        }
+
+        let p = C::p();
+        let zinv = utils::inverse_mod(self.z.clone(), p.clone())?;
+        let zinv2 = (&zinv * &zinv).mod_floor(&p);
+        let zinv3 = (&zinv2 * &zinv).mod_floor(&p);
+
+        Ok(Point::from(PointValue::Value {
+            x: (&self.x * &zinv2).mod_floor(&p),
+            y: (&self.y * &zinv3).mod_floor(&p),
+        }))
+    }
+
+    pub fn double(&self) -> Self {
+        if self.is_infinity() || self.y.is_zero() {
+            return Self::infinity();
+        }
+
+        let p = C::p();
+        let y2 = (&self.y * &self.y).mod_floor(&p);
+        let s = (BigInt::from(4u32) * &self.x * &y2).mod_floor(&p);
+        let z2 = (&self.z * &self.z).mod_floor(&p);
+        let z4 = (&z2 * &z2).mod_floor(&p);
+        let m = (BigInt::from(3u32) * &self.x * &self.x + C::a() * &z4).mod_floor(&p);
+        let x3 = (&m * &m - BigInt::from(2u32) * &s).mod_floor(&p);
+        let y4 = (&y2 * &y2).mod_floor(&p);
+        let y3 = (&m * (&s - &x3) - BigInt::from(8u32) * &y4).mod_floor(&p);
+        let z3 = (BigInt::from(2u32) * &self.y * &self.z).mod_floor(&p);
+
+        Self { x: x3, y: y3, z: z3, _marker: PhantomData }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        if self.is_infinity() {
+            return other.clone();
+        }
+        if other.is_infinity() {
+            return self.clone();
+        }
+
+        let p = C::p();
+        let z1z1 = (&self.z * &self.z).mod_floor(&p);
+        let z2z2 = (&other.z * &other.z).mod_floor(&p);
+        let u1 = (&self.x * &z2z2).mod_floor(&p);
+        let u2 = (&other.x * &z1z1).mod_floor(&p);
+        let s1 = (&self.y * &other.z * &z2z2).mod_floor(&p);
+        let s2 = (&other.y * &self.z * &z1z1).mod_floor(&p);
+
+        if u1 == u2 {
+            return if (&s1 + &s2).mod_floor(&p) == BigInt::zero() {
+                Self::infinity()
+            } else {
+                self.double()
+            };
+        }
+
+        let h = (&u2 - &u1).mod_floor(&p);
+        let r = (&s2 - &s1).mod_floor(&p);
+        let h2 = (&h * &h).mod_floor(&p);
+        let h3 = (&h2 * &h).mod_floor(&p);
+        let u1h2 = (&u1 * &h2).mod_floor(&p);
+        let x3 = (&r * &r - &h3 - BigInt::from(2u32) * &u1h2).mod_floor(&p);
+        let y3 = (&r * (&u1h2 - &x3) - &s1 * &h3).mod_floor(&p);
+        let z3 = (&h * &self.z * &other.z).mod_floor(&p);
+
+        Self { x: x3, y: y3, z: z3, _marker: PhantomData }
+    }
+}
@@ -1,6 +1,13 @@
+pub mod ecdsa;
+mod edwards;
+mod error;
+mod jacobian;
+mod scalar;
+#[cfg(test)]
+mod test_curves;
 mod utils;
 
-use num_bigint::BigInt;
+use num_bigint::{BigInt, Sign};
 use num_traits::{Zero, One};
 use num_integer::Integer;
 
@@ -8,6 +15,10 @@ use core::ops::{Add, Mul};
 use core::marker::PhantomData;
 use core::fmt::Debug;
 
+use jacobian::Jacobian;
+pub use error::EcError;
+pub use scalar::Scalar;
+
 /// A point value.
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum PointValue {
@@ -49,6 +60,11 @@ impl<C: Curve> Point<C> {
         }
     }
 
+    /// The generator of `C`'s base-point subgroup, of order `C::n()`.
+    pub fn generator() -> Self {
+        C::g()
+    }
+
     pub fn is_infinity(&self) -> bool {
         match self.value {
             PointValue::Infinity => true,
@@ -56,111 +72,326 @@ impl<C: Curve> Point<C> {
         }
     }
 
+    /// The identity element of `C`'s group: the point at infinity for
+    /// short Weierstrass curves, or `(0, 1)` for twisted Edwards curves.
+    pub fn identity() -> Self {
+        match C::model() {
+            CurveModel::ShortWeierstrass => Self::infinity(),
+            CurveModel::TwistedEdwards => edwards::identity(),
+        }
+    }
+
+    /// The negation of this point: `(x, -y)` for short Weierstrass
+    /// curves, `(-x, y)` for twisted Edwards curves.
+    pub fn neg(&self) -> Self {
+        match self.value {
+            PointValue::Infinity => Self::infinity(),
+            PointValue::Value { ref x, ref y } => match C::model() {
+                CurveModel::ShortWeierstrass => Self::from(PointValue::Value {
+                    x: x.clone(),
+                    y: (C::p() - y).mod_floor(&C::p()),
+                }),
+                CurveModel::TwistedEdwards => Self::from(PointValue::Value {
+                    x: (C::p() - x).mod_floor(&C::p()),
+                    y: y.clone(),
+                }),
+            },
+        }
+    }
+
     pub fn is_valid(&self) -> bool {
         match self.value {
-            PointValue::Infinity => true,
-            PointValue::Value { ref x, ref y } => {
-                (y * y - (x * x * x + &C::a() * x + &C::b())).mod_floor(&C::p()) == BigInt::zero()
+            PointValue::Infinity => matches!(C::model(), CurveModel::ShortWeierstrass),
+            PointValue::Value { ref x, ref y } => match C::model() {
+                CurveModel::ShortWeierstrass => {
+                    (y * y - (x * x * x + &C::a() * x + &C::b())).mod_floor(&C::p()) == BigInt::zero()
+                },
+                CurveModel::TwistedEdwards => edwards::is_valid::<C>(x, y),
             },
         }
     }
 
-    pub fn double(&self) -> Self {
+    pub fn double(&self) -> Result<Self, EcError> {
+        match C::model() {
+            CurveModel::ShortWeierstrass => {
+                let (x, y) = match self.value {
+                    PointValue::Infinity => return Ok(Self::infinity()),
+                    PointValue::Value { ref x, ref y } => (x.clone(), y.clone()),
+                };
+
+                if y.is_zero() {
+                    return Ok(Self::infinity());
+                }
+
+                let l_inv = utils::inverse_mod(BigInt::from(2u32) * &y, C::p())?;
+                let l = ((BigInt::from(3u32) * &x * &x + C::a()) * l_inv).mod_floor(&C::p());
+                let x3 = (&l * &l - BigInt::from(2u32) * &x).mod_floor(&C::p());
+                let y3 = (&l * (&x - &x3) - &y).mod_floor(&C::p());
+
+                Ok(Self::from(PointValue::Value { x: x3, y: y3 }))
+            },
+            // The unified Edwards addition law handles doubling directly.
+            CurveModel::TwistedEdwards => edwards::add(self, self),
+        }
+    }
+
+    /// Scalar multiplication using a width-2 non-adjacent-form (NAF)
+    /// recoding of the scalar, so that on average only a third of the
+    /// digits processed are nonzero. For short Weierstrass curves this
+    /// runs in Jacobian coordinates and only converts back to affine
+    /// once, at the end.
+    pub fn mul_naf(&self, scalar: &BigInt) -> Result<Self, EcError> {
+        assert!(*scalar >= BigInt::zero());
+
+        let mut k = scalar.clone();
+        let mut digits = Vec::new();
+        let four = BigInt::from(4u32);
+
+        while k > BigInt::zero() {
+            let d = if k.is_odd() {
+                let d = BigInt::from(2u32) - k.mod_floor(&four);
+                k -= &d;
+                d
+            } else {
+                BigInt::zero()
+            };
+            digits.push(d);
+            k = &k >> 1usize;
+        }
+
+        match C::model() {
+            CurveModel::ShortWeierstrass => {
+                let base = Jacobian::from_affine(self);
+                let neg = Jacobian::from_affine(&self.neg());
+
+                let mut ret = Jacobian::infinity();
+                for d in digits.into_iter().rev() {
+                    ret = ret.double();
+                    if d == BigInt::one() {
+                        ret = ret.add(&base);
+                    } else if d == -BigInt::one() {
+                        ret = ret.add(&neg);
+                    }
+                }
+                ret.to_affine()
+            },
+            CurveModel::TwistedEdwards => {
+                let neg = self.neg();
+
+                let mut ret = Self::identity();
+                for d in digits.into_iter().rev() {
+                    ret = ret.double()?;
+                    if d == BigInt::one() {
+                        ret = (ret + self.clone())?;
+                    } else if d == -BigInt::one() {
+                        ret = (ret + neg.clone())?;
+                    }
+                }
+                Ok(ret)
+            },
+        }
+    }
+
+    /// SEC1 serialization: `0x04 || x || y` uncompressed, or
+    /// `0x02`/`0x03 || x` compressed (the prefix byte encoding the parity
+    /// of `y`), with each coordinate big-endian padded to the byte
+    /// length of `C::p()`. The point at infinity serializes to `0x00`.
+    /// For `CurveModel::TwistedEdwards` curves `y` is still recovered
+    /// from `x` via the curve equation, so the same encoding applies.
+    pub fn to_bytes(&self, compressed: bool) -> Vec<u8> {
         let (x, y) = match self.value {
-            PointValue::Infinity => return Self::infinity(),
-            PointValue::Value { ref x, ref y } => (x.clone(), y.clone()),
+            PointValue::Infinity => return vec![0x00],
+            PointValue::Value { ref x, ref y } => (x, y),
         };
 
-        let l = ((BigInt::from(3u32) * &x * &x + C::a()) * utils::inverse_mod(BigInt::from(2u32) * &y, C::p())).mod_floor(&C::p());
-        let x3 = (&l * &l - BigInt::from(2u32) * &x).mod_floor(&C::p());
-        let y3 = (&l * (&x - &x3) - &y).mod_floor(&C::p());
+        let byte_len = Self::byte_len();
+        if compressed {
+            let mut out = Vec::with_capacity(1 + byte_len);
+            out.push(if y.is_even() { 0x02 } else { 0x03 });
+            out.extend(pad_be(x, byte_len));
+            out
+        } else {
+            let mut out = Vec::with_capacity(1 + 2 * byte_len);
+            out.push(0x04);
+            out.extend(pad_be(x, byte_len));
+            out.extend(pad_be(y, byte_len));
+            out
+        }
+    }
 
-        Self::from(PointValue::Value { x: x3, y: y3 })
+    /// Parse a point from its SEC1 encoding, decompressing `y` from `x`
+    /// when given a compressed point. Returns `EcError::InvalidEncoding`
+    /// on malformed input and `EcError::PointNotOnCurve` when the
+    /// decoded (or decompressed) coordinates don't satisfy the curve
+    /// equation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EcError> {
+        let byte_len = Self::byte_len();
+
+        match bytes {
+            [0x00] => Ok(Self::infinity()),
+            [0x04, rest @ ..] if rest.len() == 2 * byte_len => {
+                let x = BigInt::from_bytes_be(Sign::Plus, &rest[..byte_len]);
+                let y = BigInt::from_bytes_be(Sign::Plus, &rest[byte_len..]);
+                let point = Self::from(PointValue::Value { x, y });
+                if point.is_valid() { Ok(point) } else { Err(EcError::PointNotOnCurve) }
+            },
+            [prefix @ (0x02 | 0x03), rest @ ..] if rest.len() == byte_len => {
+                let p = C::p();
+                let x = BigInt::from_bytes_be(Sign::Plus, rest);
+                let rhs = match C::model() {
+                    CurveModel::ShortWeierstrass => {
+                        (&x * &x * &x + C::a() * &x + C::b()).mod_floor(&p)
+                    },
+                    CurveModel::TwistedEdwards => {
+                        // ax^2 + y^2 = 1 + d*x^2*y^2 solved for y^2:
+                        // y^2 = (1 - a*x^2) / (1 - d*x^2)
+                        let xx = (&x * &x).mod_floor(&p);
+                        let num = (BigInt::one() - C::a() * &xx).mod_floor(&p);
+                        let den = (BigInt::one() - C::b() * &xx).mod_floor(&p);
+                        let den_inv = utils::inverse_mod(den, p.clone())?;
+                        (num * den_inv).mod_floor(&p)
+                    },
+                };
+                let y = utils::mod_sqrt(&rhs, &p).ok_or(EcError::PointNotOnCurve)?;
+                let y = if y.is_even() == (*prefix == 0x02) { y } else { &p - &y };
+                Ok(Self::from(PointValue::Value { x, y }))
+            },
+            _ => Err(EcError::InvalidEncoding),
+        }
+    }
+
+    fn byte_len() -> usize {
+        C::p().bits().div_ceil(8) as usize
     }
 }
 
+/// Big-endian bytes of `n`, left-padded with zeroes to `len` bytes.
+fn pad_be(n: &BigInt, len: usize) -> Vec<u8> {
+    let (_, bytes) = n.to_bytes_be();
+    let mut out = vec![0u8; len.saturating_sub(bytes.len())];
+    out.extend_from_slice(&bytes);
+    out
+}
+
 impl<C: Curve> Add for Point<C> {
-    type Output = Point<C>;
+    type Output = Result<Point<C>, EcError>;
+
+    fn add(self, other: Point<C>) -> Result<Point<C>, EcError> {
+        if let CurveModel::TwistedEdwards = C::model() {
+            return edwards::add(&self, &other);
+        }
 
-    fn add(self, other: Point<C>) -> Point<C> {
         let (ox, oy) = match other.value {
-            PointValue::Infinity => return self,
+            PointValue::Infinity => return Ok(self),
             PointValue::Value { ref x, ref y } => (x.clone(), y.clone()),
         };
 
         let (sx, sy) = match self.value {
-            PointValue::Infinity => return other,
+            PointValue::Infinity => return Ok(other),
             PointValue::Value { ref x, ref y } => (x.clone(), y.clone()),
         };
 
         if sx == ox {
             return if (sy + oy).mod_floor(&C::p()) == BigInt::zero() {
-                Point::infinity()
+                Ok(Point::infinity())
             } else {
                 self.double()
             }
         }
 
-        let l = ((&oy - &sy) * utils::inverse_mod(&ox - &sx, C::p())).mod_floor(&C::p());
+        let l_inv = utils::inverse_mod(&ox - &sx, C::p())?;
+        let l = ((&oy - &sy) * l_inv).mod_floor(&C::p());
         let x3 = (&l * &l - &sx - &ox).mod_floor(&C::p());
         let y3 = (&l * (&sx - &x3) - &sy).mod_floor(&C::p());
 
-        Self::from(PointValue::Value { x: x3, y: y3 })
+        Ok(Self::from(PointValue::Value { x: x3, y: y3 }))
     }
 }
 
 impl<C: Curve> Mul<BigInt> for Point<C> {
-    type Output = Point<C>;
-
-    fn mul(self, mut other: BigInt) -> Point<C> {
+    type Output = Result<Point<C>, EcError>;
+
+    /// Scalar multiplication by binary double-and-add, scanning the
+    /// scalar's bits from most significant to least significant. For
+    /// short Weierstrass curves this runs in Jacobian coordinates and
+    /// only converts back to affine once, at the end, instead of
+    /// inverting on every addition.
+    fn mul(self, other: BigInt) -> Result<Point<C>, EcError> {
         assert!(other >= BigInt::zero());
 
-        if other == BigInt::zero() {
-            Self::infinity()
-        } else {
-            let mut ret = self.clone();
-            other -= BigInt::one();
-            while other > BigInt::zero() {
-                ret = ret + self.clone();
-                other -= BigInt::one();
-            }
-            ret
+        match C::model() {
+            CurveModel::ShortWeierstrass => {
+                let base = Jacobian::from_affine(&self);
+                let mut ret = Jacobian::infinity();
+                for i in (0..other.bits()).rev() {
+                    ret = ret.double();
+                    if other.bit(i) {
+                        ret = ret.add(&base);
+                    }
+                }
+                ret.to_affine()
+            },
+            CurveModel::TwistedEdwards => {
+                let mut ret = Self::identity();
+                for i in (0..other.bits()).rev() {
+                    ret = ret.double()?;
+                    if other.bit(i) {
+                        ret = (ret + self.clone())?;
+                    }
+                }
+                Ok(ret)
+            },
         }
     }
 }
 
-/// An elliptic curve, where y^2 = x^3 + a*x + b (mod p).
+impl<C: Curve> Mul<Scalar<C>> for Point<C> {
+    type Output = Result<Point<C>, EcError>;
+
+    fn mul(self, other: Scalar<C>) -> Result<Point<C>, EcError> {
+        self * other.value()
+    }
+}
+
+/// The algebraic form an elliptic curve's equation is given in, which
+/// determines how `Curve::a()`/`Curve::b()` are interpreted and how
+/// point addition and doubling are carried out.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum CurveModel {
+    /// `y^2 = x^3 + a*x + b`, with the point at infinity as identity.
+    ShortWeierstrass,
+    /// `a*x^2 + y^2 = 1 + b*x^2*y^2` (`b` plays the role usually called
+    /// `d`), with `(0, 1)` as identity.
+    TwistedEdwards,
+}
+
+/// An elliptic curve, where y^2 = x^3 + a*x + b (mod p) for curves in
+/// short Weierstrass form (see `CurveModel`).
 pub trait Curve: Clone + Eq + PartialEq + Debug {
     fn p() -> BigInt;
     fn a() -> BigInt;
     fn b() -> BigInt;
+
+    /// The order of the generator subgroup, i.e. the smallest positive
+    /// `n` such that `g() * n` is the identity.
+    fn n() -> BigInt;
+
+    /// The base point (generator) of the subgroup of order `n()`.
+    fn g() -> Point<Self>;
+
+    /// The curve model `a()`/`b()` should be interpreted under. Defaults
+    /// to short Weierstrass form.
+    fn model() -> CurveModel {
+        CurveModel::ShortWeierstrass
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_curves::{TestCurve, P256K1Curve};
     use num_traits::Num;
 
-    #[derive(Clone, Eq, PartialEq, Debug)]
-    /// Testing curve defined by y^2 = x^3 + 1x + 7
-    struct TestCurve;
-
-    impl Curve for TestCurve {
-        fn p() -> BigInt { BigInt::from(13u32) }
-        fn a() -> BigInt { BigInt::from(1u32) }
-        fn b() -> BigInt { BigInt::from(7u32) }
-    }
-
-    #[derive(Clone, Eq, PartialEq, Debug)]
-    /// secp256k1
-    struct P256K1Curve;
-
-    impl Curve for P256K1Curve {
-        fn p() -> BigInt { BigInt::from_str_radix("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F", 16).unwrap() }
-        fn a() -> BigInt { BigInt::zero() }
-        fn b() -> BigInt { BigInt::from(7u32) }
-    }
-
     #[test]
     fn point_addition() {
         let p1 = Point::<TestCurve>::from(PointValue::Value { x: BigInt::from(9u32), y: BigInt::from(11u32) });
@@ -168,7 +399,7 @@ mod tests {
         assert!(p1.is_valid());
         assert!(p2.is_valid());
 
-        let p3 = p1 + p2;
+        let p3 = (p1 + p2).unwrap();
 
         assert_eq!(p3.value(), Some((BigInt::from(1u32), BigInt::from(10u32))));
     }
@@ -177,20 +408,141 @@ mod tests {
     fn secp256k1() {
         let g = Point::<P256K1Curve>::from(PointValue::Value { x: BigInt::from_str_radix("79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798", 16).unwrap(), y: BigInt::from_str_radix("483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8", 16).unwrap() });
 
-        assert_eq!(g.clone() * BigInt::one(), g);
+        assert_eq!((g.clone() * BigInt::one()).unwrap(), g);
         assert_eq!(
-            g.clone() * BigInt::from(2u32),
+            (g.clone() * BigInt::from(2u32)).unwrap(),
             Point::<P256K1Curve>::from(PointValue::Value {
                 x: BigInt::from_str_radix("C6047F9441ED7D6D3045406E95C07CD85C778E4B8CEF3CA7ABAC09B95C709EE5", 16).unwrap(),
                 y: BigInt::from_str_radix("1AE168FEA63DC339A3C58419466CEAEEF7F632653266D0E1236431A950CFE52A", 16).unwrap()
             })
         );
         assert_eq!(
-            g.clone() * BigInt::from(3u32),
+            (g.clone() * BigInt::from(3u32)).unwrap(),
             Point::<P256K1Curve>::from(PointValue::Value {
                 x: BigInt::from_str_radix("F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9", 16).unwrap(),
                 y: BigInt::from_str_radix("388F7B0F632DE8140FE337E62A37F3566500A99934C2231B6CB9FD7584B8E672", 16).unwrap()
             })
         );
+
+        assert_eq!(g.clone().mul_naf(&BigInt::from(3u32)).unwrap(), (g.clone() * BigInt::from(3u32)).unwrap());
+        assert_eq!(g.clone().mul_naf(&BigInt::from(12345u32)).unwrap(), (g.clone() * BigInt::from(12345u32)).unwrap());
+    }
+
+    #[test]
+    fn generator_and_scalar() {
+        let g = Point::<TestCurve>::generator();
+        assert!(g.is_valid());
+
+        let d = Scalar::<TestCurve>::new(BigInt::from(4u32));
+        assert_eq!((g.clone() * d.clone()).unwrap(), (g.clone() * BigInt::from(4u32)).unwrap());
+
+        // The subgroup has prime order 13, so scalars wrap around mod 13.
+        let wrapped = Scalar::<TestCurve>::new(BigInt::from(17u32));
+        assert_eq!(wrapped.value(), BigInt::from(4u32));
+    }
+
+    #[derive(Clone, Eq, PartialEq, Debug)]
+    /// `y^2 = x^3 - x mod 5`, chosen so that `(0, 0)` is a valid point
+    /// of order 2 (i.e. `y == 0`).
+    struct OrderTwoCurve;
+
+    impl Curve for OrderTwoCurve {
+        fn p() -> BigInt { BigInt::from(5u32) }
+        fn a() -> BigInt { -BigInt::one() }
+        fn b() -> BigInt { BigInt::zero() }
+        fn n() -> BigInt { BigInt::from(2u32) }
+        fn g() -> Point<Self> {
+            Point::from(PointValue::Value { x: BigInt::zero(), y: BigInt::zero() })
+        }
+    }
+
+    #[test]
+    fn double_order_two_point() {
+        let p = Point::<OrderTwoCurve>::from(PointValue::Value { x: BigInt::zero(), y: BigInt::zero() });
+        assert!(p.is_valid());
+
+        let identity = Point::<OrderTwoCurve>::infinity();
+        assert_eq!(p.double(), Ok(identity.clone()));
+        assert_eq!((p.clone() * BigInt::from(2u32)), Ok(identity));
+    }
+
+    #[test]
+    fn serialization_roundtrip() {
+        let g = Point::<TestCurve>::generator();
+
+        assert_eq!(Point::<TestCurve>::infinity().to_bytes(false), vec![0x00]);
+        assert_eq!(Point::<TestCurve>::from_bytes(&[0x00]), Ok(Point::infinity()));
+
+        let uncompressed = g.to_bytes(false);
+        assert_eq!(uncompressed, vec![0x04, 1, 3]);
+        assert_eq!(Point::<TestCurve>::from_bytes(&uncompressed), Ok(g.clone()));
+
+        let compressed = g.to_bytes(true);
+        assert_eq!(compressed, vec![0x03, 1]);
+        assert_eq!(Point::<TestCurve>::from_bytes(&compressed), Ok(g.clone()));
+
+        assert_eq!(Point::<TestCurve>::from_bytes(&[0x02, 5]), Err(EcError::PointNotOnCurve));
+    }
+
+    #[test]
+    fn serialization_roundtrip_secp256k1() {
+        let g = Point::<P256K1Curve>::generator();
+
+        let uncompressed = g.to_bytes(false);
+        assert_eq!(uncompressed.len(), 65);
+        assert_eq!(Point::<P256K1Curve>::from_bytes(&uncompressed), Ok(g.clone()));
+
+        let compressed = g.to_bytes(true);
+        assert_eq!(compressed.len(), 33);
+        assert_eq!(compressed[0], 0x02);
+        assert_eq!(Point::<P256K1Curve>::from_bytes(&compressed), Ok(g.clone()));
+    }
+
+    #[derive(Clone, Eq, PartialEq, Debug)]
+    /// BabyJubJub: a twisted Edwards curve over the BN254 scalar field,
+    /// `168700*x^2 + y^2 = 1 + 168696*x^2*y^2`.
+    struct BabyJubJub;
+
+    impl Curve for BabyJubJub {
+        fn p() -> BigInt { BigInt::from_str_radix("21888242871839275222246405745257275088548364400416034343698204186575808495617", 10).unwrap() }
+        fn a() -> BigInt { BigInt::from(168700u32) }
+        fn b() -> BigInt { BigInt::from(168696u32) }
+        fn n() -> BigInt { BigInt::from_str_radix("2736030358979909402780800718157159386076813972158567259200215660948447373041", 10).unwrap() }
+        fn g() -> Point<Self> {
+            Point::from(PointValue::Value {
+                x: BigInt::from_str_radix("5299619240641551281634865583518297030282874472190772894086521144482721001553", 10).unwrap(),
+                y: BigInt::from_str_radix("16950150798460657717958625567821834550301663161624707787222815936182638968203", 10).unwrap(),
+            })
+        }
+        fn model() -> CurveModel { CurveModel::TwistedEdwards }
+    }
+
+    #[test]
+    fn baby_jubjub() {
+        let g = Point::<BabyJubJub>::generator();
+        assert!(g.is_valid());
+
+        let identity = Point::<BabyJubJub>::identity();
+        assert!(identity.is_valid());
+        assert_eq!((g.clone() + identity.clone()).unwrap(), g);
+        assert_eq!((identity.clone() + identity.clone()).unwrap(), identity);
+
+        // g has order n, so n*g is back to the identity.
+        assert_eq!((g.clone() * BabyJubJub::n()).unwrap(), identity);
+
+        assert_eq!((g.clone() + g.clone()).unwrap(), g.double().unwrap());
+        assert_eq!(g.clone().mul_naf(&BigInt::from(5u32)).unwrap(), (g.clone() * BigInt::from(5u32)).unwrap());
+    }
+
+    #[test]
+    fn serialization_roundtrip_baby_jubjub() {
+        let g = Point::<BabyJubJub>::generator();
+
+        let uncompressed = g.to_bytes(false);
+        assert_eq!(Point::<BabyJubJub>::from_bytes(&uncompressed), Ok(g.clone()));
+
+        let compressed = g.to_bytes(true);
+        assert_eq!(compressed.len(), uncompressed.len().div_ceil(2));
+        assert_eq!(Point::<BabyJubJub>::from_bytes(&compressed), Ok(g.clone()));
     }
 }
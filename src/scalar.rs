@@ -0,0 +1,49 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use core::marker::PhantomData;
+use core::ops::{Add, Mul};
+
+use crate::{Curve, EcError, utils};
+
+/// An element of the scalar field, i.e. an integer mod the order `n` of
+/// the generator subgroup. Used for private keys, nonces and signature
+/// components, as opposed to `BigInt`s reduced mod `p`, which describe
+/// coordinates on the curve itself.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Scalar<C: Curve> {
+    value: BigInt,
+    _marker: PhantomData<C>,
+}
+
+impl<C: Curve> Scalar<C> {
+    pub fn new(value: BigInt) -> Self {
+        Self {
+            value: value.mod_floor(&C::n()),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn value(&self) -> BigInt {
+        self.value.clone()
+    }
+
+    pub fn inverse(&self) -> Result<Self, EcError> {
+        Ok(Self::new(utils::inverse_mod(self.value.clone(), C::n())?))
+    }
+}
+
+impl<C: Curve> Add for Scalar<C> {
+    type Output = Scalar<C>;
+
+    fn add(self, other: Scalar<C>) -> Scalar<C> {
+        Self::new(self.value + other.value)
+    }
+}
+
+impl<C: Curve> Mul for Scalar<C> {
+    type Output = Scalar<C>;
+
+    fn mul(self, other: Scalar<C>) -> Scalar<C> {
+        Self::new(self.value * other.value)
+    }
+}
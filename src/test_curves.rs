@@ -0,0 +1,39 @@
+//! Curve fixtures shared by the test modules in this crate, so `TestCurve`
+//! and `P256K1Curve` aren't redefined in both `lib.rs` and `ecdsa.rs`.
+#![cfg(test)]
+
+use num_bigint::BigInt;
+use num_traits::{Num, Zero};
+
+use crate::{Curve, Point, PointValue};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+/// A toy curve, `y^2 = x^3 + x + 7 mod 13`, small enough to compute by hand.
+pub(crate) struct TestCurve;
+
+impl Curve for TestCurve {
+    fn p() -> BigInt { BigInt::from(13u32) }
+    fn a() -> BigInt { BigInt::from(1u32) }
+    fn b() -> BigInt { BigInt::from(7u32) }
+    fn n() -> BigInt { BigInt::from(13u32) }
+    fn g() -> Point<Self> {
+        Point::from(PointValue::Value { x: BigInt::from(1u32), y: BigInt::from(3u32) })
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+/// secp256k1
+pub(crate) struct P256K1Curve;
+
+impl Curve for P256K1Curve {
+    fn p() -> BigInt { BigInt::from_str_radix("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F", 16).unwrap() }
+    fn a() -> BigInt { BigInt::zero() }
+    fn b() -> BigInt { BigInt::from(7u32) }
+    fn n() -> BigInt { BigInt::from_str_radix("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141", 16).unwrap() }
+    fn g() -> Point<Self> {
+        Point::from(PointValue::Value {
+            x: BigInt::from_str_radix("79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798", 16).unwrap(),
+            y: BigInt::from_str_radix("483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8", 16).unwrap(),
+        })
+    }
+}
@@ -2,7 +2,12 @@ use num_bigint::BigInt;
 use num_integer::Integer;
 use num_traits::{Zero, One};
 
-pub fn inverse_mod(mut a: BigInt, m: BigInt) -> BigInt {
+use crate::EcError;
+
+/// Compute the modular inverse of `a` mod `m` via the extended
+/// Euclidean algorithm. Returns `EcError::NotInvertible` if `a` and `m`
+/// are not coprime.
+pub fn inverse_mod(mut a: BigInt, m: BigInt) -> Result<BigInt, EcError> {
     if a < BigInt::zero() || m <= a {
         a = a.mod_floor(&m);
     }
@@ -14,8 +19,6 @@ pub fn inverse_mod(mut a: BigInt, m: BigInt) -> BigInt {
         d = c;
         c = r;
 
-        println!("{}, {}, {}", q, c, d);
-
         let (nuc, nvc, nud, nvd) = (
             &ud - &q * &uc,
             &vd - &q * &vc,
@@ -27,15 +30,74 @@ pub fn inverse_mod(mut a: BigInt, m: BigInt) -> BigInt {
         vc = nvc;
         ud = nud;
         vd = nvd;
-        println!("{}, {}, {}, {}", uc, vc, ud, vd);
     }
 
-    assert_eq!(d, BigInt::one());
-    if ud > BigInt::zero() {
+    if d != BigInt::one() {
+        return Err(EcError::NotInvertible);
+    }
+
+    Ok(if ud > BigInt::zero() {
         ud
     } else {
         ud + m
+    })
+}
+
+/// Compute a modular square root of `a` mod the prime `p`, i.e. some `y`
+/// such that `y*y == a (mod p)`. Returns `None` if `a` is not a quadratic
+/// residue mod `p`. Uses the direct `p = 3 (mod 4)` formula when
+/// possible, falling back to Tonelli-Shanks otherwise.
+pub fn mod_sqrt(a: &BigInt, p: &BigInt) -> Option<BigInt> {
+    let a = a.mod_floor(p);
+    if a.is_zero() {
+        return Some(BigInt::zero());
+    }
+
+    let two = BigInt::from(2u32);
+    let legendre = a.modpow(&((p - BigInt::one()) / &two), p);
+    if legendre != BigInt::one() {
+        return None;
+    }
+
+    if p.mod_floor(&BigInt::from(4u32)) == BigInt::from(3u32) {
+        return Some(a.modpow(&((p + BigInt::one()) / BigInt::from(4u32)), p));
+    }
+
+    // Tonelli-Shanks: write p - 1 = q * 2^s with q odd.
+    let mut q = p - BigInt::one();
+    let mut s = 0u32;
+    while q.is_even() {
+        q = &q / &two;
+        s += 1;
+    }
+
+    // Find a quadratic non-residue mod p.
+    let mut z = two.clone();
+    while z.modpow(&((p - BigInt::one()) / &two), p) != p - BigInt::one() {
+        z += BigInt::one();
     }
+
+    let mut m = s;
+    let mut c = z.modpow(&q, p);
+    let mut t = a.modpow(&q, p);
+    let mut r = a.modpow(&((&q + BigInt::one()) / &two), p);
+
+    while t != BigInt::one() {
+        let mut i = 0u32;
+        let mut tt = t.clone();
+        while tt != BigInt::one() {
+            tt = (&tt * &tt).mod_floor(p);
+            i += 1;
+        }
+
+        let b = c.modpow(&two.pow(m - i - 1), p);
+        m = i;
+        c = (&b * &b).mod_floor(p);
+        t = (&t * &c).mod_floor(p);
+        r = (&r * &b).mod_floor(p);
+    }
+
+    Some(r)
 }
 
 #[cfg(test)]
@@ -44,9 +106,30 @@ mod tests {
 
     #[test]
     fn inverse_mod_test() {
-        println!("For 5, 13");
-        assert_eq!(inverse_mod(BigInt::from(5u32), BigInt::from(13u32)), BigInt::from(8u32));
-        println!("For -5, 13");
-        assert_eq!(inverse_mod(BigInt::from(-5i32), BigInt::from(13u32)), BigInt::from(5u32));
+        assert_eq!(inverse_mod(BigInt::from(5u32), BigInt::from(13u32)), Ok(BigInt::from(8u32)));
+        assert_eq!(inverse_mod(BigInt::from(-5i32), BigInt::from(13u32)), Ok(BigInt::from(5u32)));
+    }
+
+    #[test]
+    fn inverse_mod_not_coprime() {
+        assert_eq!(inverse_mod(BigInt::from(4u32), BigInt::from(6u32)), Err(EcError::NotInvertible));
+    }
+
+    #[test]
+    fn mod_sqrt_p_3_mod_4() {
+        // p = 11 (3 mod 4); 5*5 = 25 = 3 (mod 11)
+        let p = BigInt::from(11u32);
+        let y = mod_sqrt(&BigInt::from(3u32), &p).unwrap();
+        assert_eq!((&y * &y).mod_floor(&p), BigInt::from(3u32));
+        assert!(mod_sqrt(&BigInt::from(2u32), &p).is_none());
+    }
+
+    #[test]
+    fn mod_sqrt_p_1_mod_4() {
+        // p = 17 (1 mod 4); 6*6 = 36 = 2 (mod 17)
+        let p = BigInt::from(17u32);
+        let y = mod_sqrt(&BigInt::from(2u32), &p).unwrap();
+        assert_eq!((&y * &y).mod_floor(&p), BigInt::from(2u32));
+        assert!(mod_sqrt(&BigInt::from(3u32), &p).is_none());
     }
 }